@@ -0,0 +1,21 @@
+# Secondary Terminal シェル統合: bash --init-file 用のシム
+# --init-file はログインシェルでは無視されるため、-l を付けずに起動し、
+# ログインシェル相当の読み込みをここで行ってから統合スクリプトを読み込む。
+
+if [[ -n "$SECONDARY_TERMINAL_BASH_LOGIN" ]]; then
+    unset SECONDARY_TERMINAL_BASH_LOGIN
+    [[ -r /etc/profile ]] && . /etc/profile
+    for __secondary_terminal_profile in ~/.bash_profile ~/.bash_login ~/.profile; do
+        if [[ -r "$__secondary_terminal_profile" ]]; then
+            . "$__secondary_terminal_profile"
+            break
+        fi
+    done
+    unset __secondary_terminal_profile
+else
+    [[ -r ~/.bashrc ]] && . ~/.bashrc
+fi
+
+if [[ -n "$SECONDARY_TERMINAL_SHELL_INTEGRATION" ]]; then
+    . "$SECONDARY_TERMINAL_SHELL_INTEGRATION"
+fi
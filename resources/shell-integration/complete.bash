@@ -0,0 +1,41 @@
+# Secondary Terminal シェル統合: 補完候補の取得 (bash)
+# pty-shell.py の complete 制御メッセージから `bash complete.bash LINE` で実行され、
+# LINE の末尾をカーソル位置とした補完候補を 1 行 1 件で出力する。
+# bash-completion があればコマンドごとの補完関数を使い、無ければファイル名で補完する。
+# 語の分割は空白のみで行う（クォートは解釈しない）。
+
+__secondary_terminal_line=$1
+
+if [[ -r /usr/share/bash-completion/bash_completion ]]; then
+    . /usr/share/bash-completion/bash_completion 2>/dev/null
+fi
+
+read -ra COMP_WORDS <<< "$__secondary_terminal_line"
+if [[ ${#COMP_WORDS[@]} -eq 0 || "$__secondary_terminal_line" =~ [[:space:]]$ ]]; then
+    COMP_WORDS+=("")
+fi
+COMP_CWORD=$(( ${#COMP_WORDS[@]} - 1 ))
+COMP_LINE=$__secondary_terminal_line
+COMP_POINT=${#__secondary_terminal_line}
+__secondary_terminal_cur=${COMP_WORDS[COMP_CWORD]}
+
+if [[ $COMP_CWORD -eq 0 ]]; then
+    compgen -c -- "$__secondary_terminal_cur" | sort -u
+    exit 0
+fi
+
+__secondary_terminal_cmd=${COMP_WORDS[0]}
+__secondary_terminal_spec=$(complete -p "$__secondary_terminal_cmd" 2>/dev/null)
+if [[ -z "$__secondary_terminal_spec" ]] && declare -F _completion_loader >/dev/null; then
+    _completion_loader "$__secondary_terminal_cmd" 2>/dev/null
+    __secondary_terminal_spec=$(complete -p "$__secondary_terminal_cmd" 2>/dev/null)
+fi
+
+if [[ "$__secondary_terminal_spec" =~ -F\ ([^ ]+) ]]; then
+    COMPREPLY=()
+    "${BASH_REMATCH[1]}" "$__secondary_terminal_cmd" "$__secondary_terminal_cur" \
+        "${COMP_WORDS[COMP_CWORD-1]}" 2>/dev/null
+    [[ ${#COMPREPLY[@]} -gt 0 ]] && printf '%s\n' "${COMPREPLY[@]}"
+else
+    compgen -f -- "$__secondary_terminal_cur"
+fi
@@ -0,0 +1,80 @@
+# Secondary Terminal シェル統合 (bash)
+# コマンド境界 (OSC 133) とカレントディレクトリ (OSC 7) を pty-shell.py に通知する。
+# コマンドライン中の '\' ';' 改行はそれぞれ '\\' '\x3b' '\x0a' にエスケープする。
+
+if [[ -n "$__SECONDARY_TERMINAL_INTEGRATED" || $- != *i* ]]; then
+    return
+fi
+__SECONDARY_TERMINAL_INTEGRATED=1
+
+__secondary_terminal_command_running=
+# 最初のプロンプト表示 (precmd) までは preexec を発火させない
+__secondary_terminal_at_prompt=
+
+__secondary_terminal_preexec() {
+    # プロンプト表示後の最初のコマンドだけを対象にする（PROMPT_COMMAND や補完は除外）
+    [[ -n "$__secondary_terminal_at_prompt" ]] || return
+    [[ -n "$COMP_LINE" ]] && return
+    [[ "$BASH_COMMAND" == __secondary_terminal_* ]] && return
+    # 統合の読み込み後に PROMPT_COMMAND へ追加されたもの（direnv など）も除外する
+    [[ "${PROMPT_COMMAND[*]}" == *"$BASH_COMMAND"* ]] && return
+    __secondary_terminal_at_prompt=
+
+    local cmd
+    cmd="$(HISTTIMEFORMAT= builtin history 1)"
+    if [[ "$cmd" =~ ^\ *[0-9]+\*?\ +(.*)$ ]]; then
+        cmd="${BASH_REMATCH[1]}"
+    else
+        cmd="$BASH_COMMAND"
+    fi
+    cmd="${cmd//\\/\\\\}"
+    cmd="${cmd//;/\\x3b}"
+    cmd="${cmd//$'\n'/\\x0a}"
+    printf '\e]133;C;cmdline=%s\a' "$cmd"
+    __secondary_terminal_command_running=1
+}
+
+# 実行環境 (venv / nvm など) とシェルの入れ子の深さの判定に使う変数を OSC 133;P で通知する
+__secondary_terminal_report_env() {
+    local name value fields=
+    for name in VIRTUAL_ENV CONDA_DEFAULT_ENV NVM_BIN PYENV_VERSION RBENV_VERSION SHLVL; do
+        value="${!name}"
+        fields="${fields};${name}=${value//;/\\x3b}"
+    done
+    printf '\e]133;P%s\a' "$fields"
+}
+
+__secondary_terminal_precmd() {
+    local ret=$?
+    if [[ -n "$__secondary_terminal_command_running" ]]; then
+        printf '\e]133;D;%s\a' "$ret"
+    fi
+    __secondary_terminal_command_running=
+    printf '\e]7;file://%s%s\a' "$HOSTNAME" "$PWD"
+    __secondary_terminal_report_env
+    printf '\e]133;A\a'
+    # 後続の PROMPT_COMMAND にも直前のコマンドの終了ステータスを見せる
+    return "$ret"
+}
+
+# PROMPT_COMMAND の最後に実行し、ここから先のコマンドを preexec の対象にする
+__secondary_terminal_prompt_ready() {
+    __secondary_terminal_at_prompt=1
+}
+
+# precmd は $? を受け取るため先頭、prompt_ready はユーザーの PROMPT_COMMAND の後ろに置く
+PROMPT_COMMAND="__secondary_terminal_precmd${PROMPT_COMMAND:+; $PROMPT_COMMAND}; __secondary_terminal_prompt_ready"
+
+# プロンプト終端 (= 入力開始位置)
+PS1="${PS1}\[\e]133;B\a\]"
+
+# 既存の DEBUG トラップは上書きせず、先に実行してから preexec を呼ぶ
+__secondary_terminal_debug_trap_command() {
+    [[ $# -ge 3 ]] && printf '%s' "$3"
+}
+__secondary_terminal_previous_debug_trap="$(eval "__secondary_terminal_debug_trap_command $(trap -p DEBUG)")"
+if [[ -n "$__secondary_terminal_previous_debug_trap" ]]; then
+    trap 'eval "$__secondary_terminal_previous_debug_trap"; __secondary_terminal_preexec' DEBUG
+else
+    trap '__secondary_terminal_preexec' DEBUG
+fi
@@ -0,0 +1,37 @@
+# Secondary Terminal シェル統合 (fish)
+# コマンド境界 (OSC 133) とカレントディレクトリ (OSC 7) を pty-shell.py に通知する。
+# コマンドライン中の '\' ';' 改行はそれぞれ '\\' '\x3b' '\x0a' にエスケープする。
+
+if status is-interactive; and not set -q __SECONDARY_TERMINAL_INTEGRATED
+    set -g __SECONDARY_TERMINAL_INTEGRATED 1
+
+    function __secondary_terminal_preexec --on-event fish_preexec
+        set -l cmd (string replace -a '\\' '\\\\' -- $argv[1] | string replace -a ';' '\x3b' | string join '\x0a')
+        printf '\e]133;C;cmdline=%s\a' "$cmd"
+        set -g __secondary_terminal_command_running 1
+    end
+
+    function __secondary_terminal_postexec --on-event fish_postexec
+        set -l ret $status
+        if set -q __secondary_terminal_command_running
+            printf '\e]133;D;%s\a' $ret
+            set -e __secondary_terminal_command_running
+        end
+    end
+
+    # 実行環境 (venv / nvm など) とシェルの入れ子の深さの判定に使う変数を OSC 133;P で通知する
+    function __secondary_terminal_report_env
+        set -l fields
+        for name in VIRTUAL_ENV CONDA_DEFAULT_ENV NVM_BIN PYENV_VERSION RBENV_VERSION SHLVL
+            set -l value (string replace -a ';' '\x3b' -- "$$name")
+            set fields "$fields;$name=$value"
+        end
+        printf '\e]133;P%s\a' "$fields"
+    end
+
+    function __secondary_terminal_prompt --on-event fish_prompt
+        printf '\e]7;file://%s%s\a' (hostname) "$PWD"
+        __secondary_terminal_report_env
+        printf '\e]133;A\a'
+    end
+end
@@ -0,0 +1,173 @@
+#!/usr/bin/env python3
+"""
+pty-shell.py の帯域内シーケンスパーサー（InputSequenceParser / OscScanner / ControlStringFilter）の
+ファズターゲット
+
+検証する性質:
+  - どんな入力でも例外を送出しない
+  - 入力をどこで分割して与えても、一括で与えた場合と同じトークン列になる（非同期化しない）
+  - テキストとして流した部分とシーケンスとして取り出した部分で入力が失われない（OscScanner）
+
+    python3 scripts/fuzz_sequence_parser.py [回数] [シード]
+    python3 scripts/fuzz_sequence_parser.py --atheris ...   # atheris があればカバレッジ誘導で実行
+"""
+
+import os
+import random
+import sys
+
+sys.dont_write_bytecode = True
+sys.path.insert(0, os.path.dirname(os.path.abspath(__file__)))
+
+from pty_harness import load_pty_shell  # noqa: E402
+
+pty_shell = load_pty_shell()
+# 不正な制御メッセージの警告を stdout に出さない
+pty_shell.log = lambda message: None
+
+# シーケンスの断片を多めに混ぜて、境界付近の状態遷移を踏ませる
+FRAGMENTS = [
+    '\x1b',
+    '\x1b]',
+    '\x1b]777;',
+    '\x1b]133;',
+    '\x1b]7;',
+    '\x1b]52;c;',
+    '\x1b]8;;',
+    '\x1b]9;',
+    '\x1b]777;notify;',
+    '\x1bP',
+    '\x1bPq#0;2;0;0;0',
+    '\x1b_G',
+    '\x1b[',
+    '\x1b[8;',
+    '\x1b[8;24;80t',
+    '\x1b\\',
+    '\x07',
+    '\x00',
+    ';',
+    '{"type":"get_history"}',
+    '{"type":',
+    '{}',
+    'A',
+    'C;cmdline=ls',
+    'D;0',
+    'file:///tmp',
+    '123',
+    'あ',
+    'echo hi\n',
+]
+
+
+def random_input(rng):
+    parts = []
+    for _ in range(rng.randint(0, 30)):
+        if rng.random() < 0.8:
+            parts.append(rng.choice(FRAGMENTS))
+        else:
+            parts.append(''.join(chr(rng.randint(0, 0x7F)) for _ in range(rng.randint(1, 4))))
+    return ''.join(parts)
+
+
+def random_split(rng, text):
+    points = sorted(rng.sample(range(len(text) + 1), min(len(text) + 1, rng.randint(0, 6))))
+    chunks = []
+    previous = 0
+    for point in points:
+        chunks.append(text[previous:point])
+        previous = point
+    chunks.append(text[previous:])
+    return chunks
+
+
+def merge_text(tokens):
+    merged = []
+    for token in tokens:
+        if token[0] == 'text' and merged and merged[-1][0] == 'text':
+            merged[-1] = ('text', merged[-1][1] + token[1])
+        elif token[0] != 'text' or token[1]:
+            merged.append(token)
+    return merged
+
+
+def run_input_parser(chunks, controls):
+    parser = pty_shell.InputSequenceParser(controls=controls)
+    tokens = []
+    for chunk in chunks:
+        tokens.extend(parser.feed(chunk))
+    tokens.extend(parser.flush())
+    return merge_text(tokens), parser.pending, parser.discarding
+
+
+def run_osc_scanner(chunks):
+    scanner = pty_shell.OscScanner()
+    tokens = []
+    for chunk in chunks:
+        tokens.extend(scanner.feed(chunk))
+    return merge_text(tokens), scanner.pending
+
+
+def strip_osc9(kind, body):
+    return '' if kind == 'osc' and body.startswith('9;') else None
+
+
+def run_control_string_filter(chunks):
+    control_filter = pty_shell.ControlStringFilter()
+    control_filter.handlers.append(strip_osc9)
+    output = ''.join(control_filter.feed(chunk) for chunk in chunks)
+    return output, control_filter.pending, control_filter.passthrough
+
+
+def check(text, chunks):
+    for controls in (True, False):
+        whole = run_input_parser([text], controls)
+        split = run_input_parser(chunks, controls)
+        assert whole == split, (text, chunks, whole, split)
+
+    whole = run_osc_scanner([text])
+    split = run_osc_scanner(chunks)
+    assert whole == split, (text, chunks, whole, split)
+    tokens, pending = whole
+    # OSC として取り出したもの以外は、そのままの順序で text に残る
+    rebuilt = ''.join(
+        token[1] if token[0] == 'text' else f'\x1b]{token[1]};{token[2]}' for token in tokens
+    )
+    stripped = pty_shell.OscScanner.OSC_PATTERN.sub(
+        lambda m: f'\x1b]{m.group(1)};{m.group(2)}', text
+    )
+    assert rebuilt + pending == stripped, (text, rebuilt, pending)
+
+    whole = run_control_string_filter([text])
+    split = run_control_string_filter(chunks)
+    assert whole == split, (text, chunks, whole, split)
+
+
+def fuzz_random(iterations, seed):
+    rng = random.Random(seed)
+    for _ in range(iterations):
+        text = random_input(rng)
+        check(text, random_split(rng, text))
+    print(f'ok: {iterations} inputs (seed {seed})')
+
+
+def fuzz_atheris():
+    import atheris
+
+    def test_one_input(data):
+        provider = atheris.FuzzedDataProvider(data)
+        text = provider.ConsumeUnicodeNoSurrogates(provider.remaining_bytes())
+        rng = random.Random(len(text))
+        check(text, random_split(rng, text))
+
+    atheris.Setup(sys.argv, test_one_input)
+    atheris.Fuzz()
+
+
+if __name__ == '__main__':
+    if '--atheris' in sys.argv:
+        sys.argv.remove('--atheris')
+        fuzz_atheris()
+    else:
+        iterations = int(sys.argv[1]) if len(sys.argv) > 1 else 20000
+        seed = int(sys.argv[2]) if len(sys.argv) > 2 else random.randrange(1 << 30)
+        fuzz_random(iterations, seed)